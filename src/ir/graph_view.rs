@@ -0,0 +1,144 @@
+//! Interactive node-link rendering of a compiled flow's per-location IR.
+//!
+//! One [`DfirGraph`] is produced per location id returned by flows like
+//! `complex_flow` or `multi_process_flow`. A naive walk of a [`HydroNode`] tree
+//! re-visits the entire upstream subtree every time a stream was `.clone()`'d
+//! (e.g. `data.clone()` in `complex_flow`), since clones lower to a shared
+//! `HydroNode::Tee` and a plain recursive walk has no notion of "already drew
+//! this". We dedupe by the identity of the `Rc` each `Tee` wraps: the first time
+//! a tee is reached we assign it a stable id and render it in full; every later
+//! encounter just emits an edge back to that id instead of re-walking the
+//! subtree, which keeps the rendered graph linear in node count.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use hydro_lang::ir::{HydroLeaf, HydroNode};
+
+/// A single rendered node, keyed by a stable id so the webview can draw edges
+/// without re-walking the IR.
+#[derive(Debug, Clone)]
+pub struct GraphNode {
+    pub id: usize,
+    pub label: String,
+    /// Ids of the nodes that feed into this one (its inputs, upstream direction).
+    pub children: Vec<usize>,
+}
+
+/// The rendered sub-graph for a single `Process`/`Cluster` location.
+#[derive(Debug, Clone, Default)]
+pub struct DfirGraph {
+    pub location_id: usize,
+    pub nodes: Vec<GraphNode>,
+}
+
+thread_local! {
+    /// Maps a tee's `Rc` pointer identity to the stable id it was first assigned.
+    /// Cleared at the start of each [`render_dfir_graphs`] call so ids don't leak
+    /// across unrelated renders.
+    static SEEN_TEES: RefCell<HashMap<usize, usize>> = RefCell::new(HashMap::new());
+}
+
+/// Renders the per-location leaves from `built.preview_compile().all_dfir()` into
+/// one [`DfirGraph`] per location, deduplicating shared tees along the way.
+pub fn render_dfir_graphs(leaves_by_location: &[(usize, Vec<HydroLeaf>)]) -> Vec<DfirGraph> {
+    leaves_by_location
+        .iter()
+        .map(|(location_id, leaves)| {
+            // Each location gets its own `nodes` vec, so the dedup map must be
+            // reset per location too - otherwise an id handed back for a tee
+            // seen in an earlier location would index into that earlier
+            // location's `nodes`, not this one's.
+            SEEN_TEES.with(|seen| seen.borrow_mut().clear());
+
+            let mut nodes = Vec::new();
+            for leaf in leaves {
+                walk_leaf(leaf, &mut nodes);
+            }
+            DfirGraph {
+                location_id: *location_id,
+                nodes,
+            }
+        })
+        .collect()
+}
+
+fn walk_leaf(leaf: &HydroLeaf, nodes: &mut Vec<GraphNode>) -> usize {
+    let id = nodes.len();
+    nodes.push(GraphNode {
+        id,
+        label: leaf.print_root(),
+        children: vec![],
+    });
+    let input_id = walk_node(leaf.input(), nodes);
+    nodes[id].children.push(input_id);
+    id
+}
+
+fn walk_node(node: &HydroNode, nodes: &mut Vec<GraphNode>) -> usize {
+    if let HydroNode::Tee { inner, .. } = node {
+        let key = Rc::as_ptr(inner) as usize;
+        if let Some(existing_id) = SEEN_TEES.with(|seen| seen.borrow().get(&key).copied()) {
+            return existing_id;
+        }
+
+        // Reserve the slot before recursing so a tee that (indirectly) feeds
+        // back into itself still resolves to a stable, already-known id.
+        let id = nodes.len();
+        nodes.push(GraphNode {
+            id,
+            label: "tee".to_string(),
+            children: vec![],
+        });
+        SEEN_TEES.with(|seen| seen.borrow_mut().insert(key, id));
+
+        let inner_id = walk_node(&inner.borrow(), nodes);
+        nodes[id].children.push(inner_id);
+        return id;
+    }
+
+    let id = nodes.len();
+    nodes.push(GraphNode {
+        id,
+        label: node.print_root(),
+        children: vec![],
+    });
+    let child_ids: Vec<usize> = node
+        .input_nodes()
+        .map(|child| walk_node(child, nodes))
+        .collect();
+    nodes[id].children.extend(child_ids);
+    id
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two tees wrapping the same `Rc` should collapse to one tee node.
+    #[test]
+    fn dedup_keys_on_rc_identity_not_value() {
+        SEEN_TEES.with(|seen| seen.borrow_mut().clear());
+
+        let shared: Rc<RefCell<HydroNode>> = Rc::new(RefCell::new(HydroNode::Placeholder));
+        let mut nodes = Vec::new();
+
+        let first = walk_node(
+            &HydroNode::Tee {
+                inner: Rc::clone(&shared),
+            },
+            &mut nodes,
+        );
+        let second = walk_node(
+            &HydroNode::Tee {
+                inner: Rc::clone(&shared),
+            },
+            &mut nodes,
+        );
+
+        assert_eq!(first, second);
+        // Only the tee wrapper + the one shared `Placeholder` underneath it.
+        assert_eq!(nodes.len(), 2);
+    }
+}
@@ -0,0 +1,7 @@
+//! Analysis passes over the IR produced by `hydro_lang::compile::builder::FlowBuilder`,
+//! keyed by the `(location_id, HydroLeaf)` pairs that
+//! `built.preview_compile().all_dfir()` hands back per `Process`/`Cluster`.
+
+pub mod graph_view;
+pub mod tick_overlay;
+pub mod topology;
@@ -0,0 +1,141 @@
+//! Static classification of each operator in a flow as running in the
+//! unbounded top-level domain or inside a tick, so the editor can shade the
+//! bounded regions (e.g. the `batch -> fold -> all_ticks` window on the worker
+//! and the `snapshot -> all_ticks` window on the leader in `map_reduce`).
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use hydro_lang::ir::{HydroLeaf, HydroNode};
+
+/// Which domain an operator's output lives in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Domain {
+    /// The default domain every stream starts in.
+    TopLevel,
+    /// Inside a tick, entered via `.batch(...)` or `.tick()`.
+    Tick,
+}
+
+/// The domain a single operator was classified into, identified by its
+/// position in `built.ir()` for labeling/shading in the editor.
+#[derive(Debug, Clone)]
+pub struct DomainSpan {
+    pub op_label: String,
+    pub domain: Domain,
+}
+
+/// Walks every leaf in a flow's IR and classifies each operator it passes
+/// through as [`Domain::TopLevel`] or [`Domain::Tick`].
+///
+/// `.batch(...)` and `.tick()` enter the tick domain; `.all_ticks()` and
+/// `.snapshot(...)` exit back to the top level. Each node's domain is derived
+/// solely from its own operator kind and the domain of its own first input
+/// ([`domain_of`]) - never from a sibling branch elsewhere in the tree, so in
+/// a node like `ids.cross_product(numbers)` with one ticked input and one
+/// top-level input each branch keeps its own domain. The domain of a
+/// multi-input node itself follows its first input only; the other inputs
+/// are not consulted.
+pub fn classify_domains(leaves: &[HydroLeaf]) -> Vec<DomainSpan> {
+    let mut domain_memo = HashMap::new();
+    let mut listed = HashMap::new();
+    let mut spans = Vec::new();
+    for leaf in leaves {
+        collect_spans(leaf.input(), &mut domain_memo, &mut listed, &mut spans);
+    }
+    spans
+}
+
+/// Post-order walk that lists every distinct node once (tees deduped by `Rc`
+/// pointer identity, same as [`super::graph_view`]/[`super::topology`]) and
+/// classifies each with [`domain_of`].
+fn collect_spans(
+    node: &HydroNode,
+    domain_memo: &mut HashMap<usize, Domain>,
+    listed: &mut HashMap<usize, ()>,
+    spans: &mut Vec<DomainSpan>,
+) {
+    if let HydroNode::Tee { inner, .. } = node {
+        let key = Rc::as_ptr(inner) as usize;
+        if listed.contains_key(&key) {
+            return;
+        }
+        listed.insert(key, ());
+        collect_spans(&inner.borrow(), domain_memo, listed, spans);
+        return;
+    }
+
+    for child in node.input_nodes() {
+        collect_spans(child, domain_memo, listed, spans);
+    }
+
+    spans.push(DomainSpan {
+        op_label: node.print_root(),
+        domain: domain_of(node, domain_memo),
+    });
+}
+
+/// Computes the domain of `node`'s own output from its own operator kind and
+/// the domain of its own first input, recursing down that input rather than
+/// threading a single domain value across an arbitrary flattened ordering of
+/// the whole tree; any further inputs are not consulted. Tee domains are memoized by `Rc` pointer
+/// identity so a shared subtree is only classified once.
+fn domain_of(node: &HydroNode, memo: &mut HashMap<usize, Domain>) -> Domain {
+    if let HydroNode::Tee { inner, .. } = node {
+        let key = Rc::as_ptr(inner) as usize;
+        if let Some(&cached) = memo.get(&key) {
+            return cached;
+        }
+        let domain = domain_of(&inner.borrow(), memo);
+        memo.insert(key, domain);
+        return domain;
+    }
+
+    match node {
+        HydroNode::Batch { .. } | HydroNode::Tick { .. } => Domain::Tick,
+        HydroNode::AllTicks { .. } | HydroNode::Snapshot { .. } => Domain::TopLevel,
+        // A source with no inputs starts in the top-level domain; anything
+        // else inherits from its own input, not from an unrelated sibling.
+        _ => node
+            .input_nodes()
+            .next()
+            .map(|child| domain_of(child, memo))
+            .unwrap_or(Domain::TopLevel),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Mirrors a two-branch node like `ids.cross_product(numbers)` in
+    /// `simple_cluster`: one input went through `.batch(...)`, the other
+    /// never did. Each branch must be classified from its own ancestry, not
+    /// from whichever branch happens to come first/last in a flattened walk.
+    #[test]
+    fn domain_does_not_leak_across_sibling_branches() {
+        let mut memo = HashMap::new();
+
+        let ticked_branch = HydroNode::Batch {
+            input: Box::new(HydroNode::Placeholder),
+        };
+        let top_level_branch = HydroNode::Placeholder;
+
+        assert_eq!(domain_of(&ticked_branch, &mut memo), Domain::Tick);
+        assert_eq!(domain_of(&top_level_branch, &mut memo), Domain::TopLevel);
+    }
+
+    #[test]
+    fn all_ticks_exits_back_to_top_level() {
+        let mut memo = HashMap::new();
+
+        let batched = HydroNode::Batch {
+            input: Box::new(HydroNode::Placeholder),
+        };
+        let exited = HydroNode::AllTicks {
+            input: Box::new(batched),
+        };
+
+        assert_eq!(domain_of(&exited, &mut memo), Domain::TopLevel);
+    }
+}
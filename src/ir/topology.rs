@@ -0,0 +1,208 @@
+//! Process/cluster topology diagrams, distinct from the per-location dataflow
+//! graphs in [`super::graph_view`]: every `Process`/`Cluster` is a box, and every
+//! cross-location transfer becomes a labeled edge between two boxes instead of a
+//! node in either location's own graph.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use hydro_lang::ir::{HydroLeaf, HydroNetworkType, HydroNode};
+
+/// How records are routed across a network edge.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FanOut {
+    /// `send_bincode` / `decouple_process` / `decouple_cluster`: every sender
+    /// talks to exactly one fixed receiver.
+    OneToOne,
+    /// `demux_bincode`: the payload carries the destination member id.
+    Demux,
+    /// `round_robin_bincode`: senders cycle through receivers in turn.
+    RoundRobin,
+    /// `send_partitioned`: a user-supplied policy picks the receiver.
+    Partitioned,
+}
+
+/// A box in the topology diagram: one per `Process`/`Cluster` location id.
+#[derive(Debug, Clone)]
+pub struct LocationBox {
+    pub location_id: usize,
+    pub is_cluster: bool,
+    /// Set when this location's IR contains a `source_cluster_members` source,
+    /// so the diagram can flag it as replicated/self-aware. Uses of
+    /// `CLUSTER_SELF_ID` are not detected and do not set this flag.
+    pub reads_membership: bool,
+}
+
+/// A labeled edge between two [`LocationBox`]es.
+#[derive(Debug, Clone)]
+pub struct NetworkEdge {
+    pub from_location: usize,
+    pub to_location: usize,
+    /// e.g. `"send_bincode"`, `"demux_bincode"`.
+    pub wire_encoding: &'static str,
+    pub fan_out: FanOut,
+}
+
+/// The full topology diagram for a compiled flow.
+#[derive(Debug, Clone, Default)]
+pub struct TopologyGraph {
+    pub locations: Vec<LocationBox>,
+    pub edges: Vec<NetworkEdge>,
+}
+
+/// Builds a [`TopologyGraph`] from the per-location leaves in
+/// `built.preview_compile().all_dfir()`-style output.
+pub fn render_topology(leaves_by_location: &[(usize, bool, Vec<HydroLeaf>)]) -> TopologyGraph {
+    let mut graph = TopologyGraph::default();
+
+    for (location_id, is_cluster, leaves) in leaves_by_location {
+        // A fresh `visited` set per location: a tee already walked for one
+        // location must still be walked for the next one, or that location's
+        // `Network` edges and membership reads under the shared tee would be
+        // silently dropped instead of just mis-rendered.
+        let visited: RefCell<HashMap<usize, ()>> = RefCell::new(HashMap::new());
+        let mut reads_membership = false;
+        for leaf in leaves {
+            walk_leaf(leaf, *location_id, &visited, &mut graph, &mut reads_membership);
+        }
+        graph.locations.push(LocationBox {
+            location_id: *location_id,
+            is_cluster: *is_cluster,
+            reads_membership,
+        });
+    }
+
+    graph
+}
+
+fn walk_leaf(
+    leaf: &HydroLeaf,
+    location_id: usize,
+    visited: &RefCell<HashMap<usize, ()>>,
+    graph: &mut TopologyGraph,
+    reads_membership: &mut bool,
+) {
+    walk_node(leaf.input(), location_id, visited, graph, reads_membership);
+}
+
+fn walk_node(
+    node: &HydroNode,
+    location_id: usize,
+    visited: &RefCell<HashMap<usize, ()>>,
+    graph: &mut TopologyGraph,
+    reads_membership: &mut bool,
+) {
+    if let HydroNode::Tee { inner, .. } = node {
+        let key = Rc::as_ptr(inner) as usize;
+        if visited.borrow().contains_key(&key) {
+            return;
+        }
+        visited.borrow_mut().insert(key, ());
+        walk_node(&inner.borrow(), location_id, visited, graph, reads_membership);
+        return;
+    }
+
+    if let HydroNode::Network {
+        network_type,
+        to_location,
+        ..
+    } = node
+    {
+        let (wire_encoding, fan_out) = match network_type {
+            HydroNetworkType::SendBincode => ("send_bincode", FanOut::OneToOne),
+            HydroNetworkType::DemuxBincode => ("demux_bincode", FanOut::Demux),
+            HydroNetworkType::RoundRobinBincode => ("round_robin_bincode", FanOut::RoundRobin),
+            HydroNetworkType::SendPartitioned => ("send_partitioned", FanOut::Partitioned),
+            HydroNetworkType::DecoupleCluster => ("decouple_cluster", FanOut::OneToOne),
+            HydroNetworkType::DecoupleProcess => ("decouple_process", FanOut::OneToOne),
+        };
+        graph.edges.push(NetworkEdge {
+            from_location: location_id,
+            to_location: *to_location,
+            wire_encoding,
+            fan_out,
+        });
+    }
+
+    if matches!(node, HydroNode::SourceClusterMembers { .. }) {
+        *reads_membership = true;
+    }
+
+    for child in node.input_nodes() {
+        walk_node(child, location_id, visited, graph, reads_membership);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Two locations' leaves share the same `Tee` `Rc` (e.g. both read from a
+    /// `.clone()`'d stream). Location 1 walking it first must not cause
+    /// location 2's own, separately-scoped walk to treat the pointer as
+    /// already seen and silently drop the `Network` edge underneath it.
+    #[test]
+    fn each_location_sees_its_own_edges_under_a_tee_shared_with_another_location() {
+        let shared = Rc::new(RefCell::new(HydroNode::Network {
+            network_type: HydroNetworkType::SendBincode,
+            to_location: 99,
+        }));
+        let mut graph = TopologyGraph::default();
+        let mut reads_membership = false;
+
+        let visited_for_location_1 = RefCell::new(HashMap::new());
+        walk_node(
+            &HydroNode::Tee {
+                inner: Rc::clone(&shared),
+            },
+            1,
+            &visited_for_location_1,
+            &mut graph,
+            &mut reads_membership,
+        );
+
+        let visited_for_location_2 = RefCell::new(HashMap::new());
+        walk_node(
+            &HydroNode::Tee {
+                inner: Rc::clone(&shared),
+            },
+            2,
+            &visited_for_location_2,
+            &mut graph,
+            &mut reads_membership,
+        );
+
+        assert_eq!(graph.edges.len(), 2);
+        assert_eq!(graph.edges[0].from_location, 1);
+        assert_eq!(graph.edges[1].from_location, 2);
+    }
+
+    /// Within a single location, the same tee reached from two leaves must
+    /// still only contribute one edge - the dedup is per-location, not
+    /// disabled altogether.
+    #[test]
+    fn same_location_still_dedupes_a_tee_reached_twice() {
+        let shared = Rc::new(RefCell::new(HydroNode::Network {
+            network_type: HydroNetworkType::DemuxBincode,
+            to_location: 7,
+        }));
+        let mut graph = TopologyGraph::default();
+        let mut reads_membership = false;
+        let visited = RefCell::new(HashMap::new());
+
+        for _ in 0..2 {
+            walk_node(
+                &HydroNode::Tee {
+                    inner: Rc::clone(&shared),
+                },
+                1,
+                &visited,
+                &mut graph,
+                &mut reads_membership,
+            );
+        }
+
+        assert_eq!(graph.edges.len(), 1);
+    }
+}
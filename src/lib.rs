@@ -0,0 +1,9 @@
+//! Hydro IDE: editor tooling for inspecting, overlaying, and testing compiled
+//! [Hydro](https://hydro.run) flows.
+//!
+//! Each module here turns something a user would otherwise have to read out of
+//! `built.ir()` / `surface_syntax_string()` snapshots by hand into an interactive
+//! editor surface instead.
+
+pub mod ir;
+pub mod source;
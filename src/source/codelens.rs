@@ -0,0 +1,239 @@
+//! CodeLens scaffolding for `#[hydro::flow]` functions (e.g. `simple_flow`,
+//! `complex_flow`, `multi_process_flow`): one click turns "I need to test this
+//! flow" into a runnable `#[test]` harness instead of a hand-written deploy
+//! setup.
+
+use quote::{format_ident, quote};
+use syn::punctuated::Punctuated;
+use syn::token::Comma;
+use syn::visit_mut::{self, VisitMut};
+use syn::{parse_quote, Attribute, Expr, ExprMethodCall, File, Item, ItemFn};
+
+/// A CodeLens anchored to one `#[hydro::flow]` function.
+#[derive(Debug, Clone)]
+pub struct FlowTestLens {
+    pub fn_name: String,
+    /// Byte offset of the function's `fn` keyword, for positioning the lens
+    /// above the signature (requires the file to have been parsed with
+    /// `proc-macro2`'s `span-locations` feature enabled).
+    pub span_start: usize,
+    /// The `#[test]` harness generated when the lens is invoked.
+    pub scaffold_source: String,
+}
+
+/// Finds every `#[hydro::flow]`-annotated function in a parsed source file and
+/// builds a lens + scaffold for each one.
+pub fn find_flow_test_lenses(file: &File) -> Vec<FlowTestLens> {
+    file.items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Fn(item_fn) if has_hydro_flow_attr(&item_fn.attrs) => Some(build_lens(item_fn)),
+            _ => None,
+        })
+        .collect()
+}
+
+fn has_hydro_flow_attr(attrs: &[Attribute]) -> bool {
+    attrs.iter().any(|attr| {
+        attr.path()
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "flow")
+    })
+}
+
+fn build_lens(item_fn: &ItemFn) -> FlowTestLens {
+    let fn_name = item_fn.sig.ident.to_string();
+    let span_start = item_fn.sig.fn_token.span.byte_range().start;
+
+    FlowTestLens {
+        fn_name,
+        span_start,
+        scaffold_source: render_scaffold(item_fn),
+    }
+}
+
+/// Rewrites every `source_iter(...)` root into an injected external byte port
+/// and every terminal `for_each(...)` sink into a capture port, numbering each
+/// kind independently so a flow with several roots/sinks (like `complex_flow`,
+/// which drains `data` into both a `send_bincode` branch and a `fold`) gets one
+/// port per occurrence instead of them colliding.
+#[derive(Default)]
+struct PortInjector {
+    /// The original `source_iter(...)` argument's source text, one per input
+    /// port, in the order encountered - these are the sample records fed into
+    /// the generated harness.
+    injected_inputs: Vec<String>,
+    next_output_port: usize,
+}
+
+impl VisitMut for PortInjector {
+    fn visit_expr_method_call_mut(&mut self, call: &mut ExprMethodCall) {
+        visit_mut::visit_expr_method_call_mut(self, call);
+
+        if call.method == "source_iter" {
+            let port_index = self.injected_inputs.len();
+            let sample = call
+                .args
+                .first()
+                .map(sample_record_text)
+                .unwrap_or_default();
+            self.injected_inputs.push(sample);
+
+            call.method = format_ident!("source_external_bytes");
+            call.args = Punctuated::<Expr, Comma>::new();
+            call.args
+                .push(parse_quote!(&external.input_port(#port_index)));
+        } else if call.method == "for_each" {
+            let port_index = self.next_output_port;
+            self.next_output_port += 1;
+
+            call.method = format_ident!("send_bincode_external");
+            call.args = Punctuated::<Expr, Comma>::new();
+            call.args
+                .push(parse_quote!(&external.output_port(#port_index)));
+        }
+    }
+}
+
+/// Pulls the sample records out of a `source_iter(...)` argument. The
+/// argument is normally a `q!(...)`-staged expression (e.g. `q!(vec![1, 2, 3])`);
+/// any other expression is returned as written.
+/// `q!` stages code for the DSL; it isn't a host-side value producer, so
+/// splicing the whole macro call into `send_all(...)` would hand it a quoted
+/// expression instead of an actual `Vec`. Strip the wrapper and keep only the
+/// inner expression.
+fn sample_record_text(arg: &Expr) -> String {
+    if let Expr::Macro(expr_macro) = arg {
+        if expr_macro.mac.path.is_ident("q") {
+            return expr_macro.mac.tokens.to_string();
+        }
+    }
+    quote!(#arg).to_string()
+}
+
+/// Renders the `#[test]` harness text inserted by the lens: a copy of the
+/// flow with its `source_iter` roots replaced by injected external byte ports
+/// and its terminal `for_each` sinks replaced by capture ports, plus a test
+/// that builds it, feeds each port the flow's own sample records, and asserts
+/// each capture port actually received output.
+fn render_scaffold(item_fn: &ItemFn) -> String {
+    let fn_name = item_fn.sig.ident.to_string();
+    let harness_fn_name = format_ident!("{fn_name}_with_ports");
+
+    let mut harness_fn = item_fn.clone();
+    harness_fn.sig.ident = harness_fn_name.clone();
+    harness_fn
+        .sig
+        .inputs
+        .push(parse_quote!(external: &hydro_lang::external_process::ExternalProcess<'a>));
+
+    let mut injector = PortInjector::default();
+    injector.visit_block_mut(&mut harness_fn.block);
+
+    let feed_lines: String = injector
+        .injected_inputs
+        .iter()
+        .enumerate()
+        .map(|(i, sample)| format!("    external.input_port({i}).send_all({sample});\n"))
+        .collect();
+
+    // Only a generic "did anything come out" assertion can be derived without
+    // re-implementing the flow's per-record transform here; tighten these to
+    // exact expected values once the harness is in the editor.
+    let assert_lines: String = (0..injector.next_output_port)
+        .map(|i| format!("    assert!(!external.output_port({i}).captured().is_empty());\n"))
+        .collect();
+
+    format!(
+        "{harness_fn}\n\n#[test]\nfn {fn_name}_harness() {{\n\
+         \x20   let builder = hydro_lang::compile::builder::FlowBuilder::new();\n\
+         \x20   let external = builder.external_process::<()>();\n\
+         \x20   let _ = super::{harness_fn_name}(&builder, &external);\n\
+         \x20   let built = builder.with_default_optimize::<hydro_lang::deploy::HydroDeploy>();\n\
+         \x20   built.deploy_and_run_sync();\n\n\
+         {feed_lines}\
+         {assert_lines}\
+         }}\n",
+        harness_fn = quote!(#harness_fn),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+
+    use super::*;
+
+    #[test]
+    fn sample_record_text_strips_the_q_macro_wrapper() {
+        let arg: Expr = parse_quote!(q!(vec![1, 2, 3, 4, 5]));
+        assert_eq!(sample_record_text(&arg), "vec ! [1 , 2 , 3 , 4 , 5]");
+    }
+
+    #[test]
+    fn sample_record_text_leaves_non_q_args_untouched() {
+        let arg: Expr = parse_quote!(some_other_expr());
+        assert_eq!(sample_record_text(&arg), "some_other_expr ()");
+    }
+
+    #[test]
+    fn render_scaffold_injects_ports_with_bare_samples() {
+        let item_fn: ItemFn = parse_quote! {
+            #[hydro::flow]
+            pub fn simple_flow<'a>(flow: &FlowBuilder<'a>) -> Process<'a, ()> {
+                let process = flow.process();
+
+                process
+                    .source_iter(q!(vec![1, 2, 3]))
+                    .map(q!(|x| x * 2))
+                    .for_each(q!(|x| println!("{}", x)));
+
+                process
+            }
+        };
+
+        let scaffold = render_scaffold(&item_fn);
+
+        assert!(scaffold.contains("source_external_bytes"));
+        assert!(scaffold.contains("send_bincode_external"));
+        // The sample fed to the harness must be the bare `Vec`, not the
+        // `q!(...)`-staged macro call that guarded it in the original flow.
+        assert!(scaffold.contains("send_all(vec ! [1 , 2 , 3])"));
+        assert!(!scaffold.contains("send_all(q !"));
+    }
+
+    #[test]
+    fn render_scaffold_numbers_multiple_sinks_independently() {
+        // Mirrors `complex_flow`, which drains `data` into both a
+        // `send_bincode` branch and a `fold` - each terminal `for_each` must
+        // get its own output port instead of colliding on port 0.
+        let item_fn: ItemFn = parse_quote! {
+            #[hydro::flow]
+            pub fn complex_flow<'a>(flow: &FlowBuilder<'a>) -> (Process<'a, ()>, Cluster<'a, ()>) {
+                let process = flow.process();
+                let cluster = flow.cluster();
+
+                let data = process
+                    .source_iter(q!(vec![1, 2, 3, 4, 5]))
+                    .map(q!(|x| x * x));
+
+                data.clone()
+                    .send_bincode(&cluster)
+                    .for_each(q!(|x| println!("Cluster: {}", x)));
+
+                data.fold(q!(|| 0), q!(|acc, x| *acc += x))
+                    .for_each(q!(|sum| println!("Sum: {}", sum)));
+
+                (process, cluster)
+            }
+        };
+
+        let scaffold = render_scaffold(&item_fn);
+
+        assert!(scaffold.contains("output_port(0usize)") || scaffold.contains("output_port(0)"));
+        assert!(scaffold.contains("output_port(1usize)") || scaffold.contains("output_port(1)"));
+        assert!(scaffold.contains("assert!(!external.output_port(0"));
+        assert!(scaffold.contains("assert!(!external.output_port(1"));
+    }
+}
@@ -0,0 +1,198 @@
+//! Workspace-scoped discovery of Hydro flows, tying together the function-,
+//! file-, and workspace-level scopes `lib.rs` advertises (`simple_flows`,
+//! `complex_flows`, `multi_process`) into one browsable tree, with a per-entry
+//! command to compile a flow and regenerate its `assert_debug_snapshot!(built.ir())`
+//! / `surface_graph` snapshots.
+
+use std::path::{Path, PathBuf};
+
+use syn::{FnArg, Item, ReturnType, Type};
+
+/// One discovered flow: a function whose signature returns `Process`/`Cluster`
+/// values built from a `FlowBuilder<'a>`. Covers both `#[hydro::flow]`
+/// functions and plain ones like `another_hydro_function`, `decouple_cluster`,
+/// and `map_reduce`.
+#[derive(Debug, Clone)]
+pub struct FlowEntry {
+    pub fn_name: String,
+    pub file: PathBuf,
+    /// Whether the function takes exactly one `&FlowBuilder<'a>` parameter and
+    /// nothing else, i.e. can be invoked with no extra arguments - functions
+    /// like `partition` that take additional cluster/policy parameters cannot.
+    pub directly_compilable: bool,
+}
+
+/// All flows discovered in one file.
+#[derive(Debug, Clone, Default)]
+pub struct FileFlows {
+    pub file: PathBuf,
+    pub entries: Vec<FlowEntry>,
+}
+
+/// The full workspace tree: one [`FileFlows`] per file that contains at least
+/// one discovered flow.
+#[derive(Debug, Clone, Default)]
+pub struct WorkspaceFlows {
+    pub files: Vec<FileFlows>,
+}
+
+/// Scans every parsed file in the workspace and builds a [`WorkspaceFlows`]
+/// tree. `files` pairs each file's path with its already-parsed `syn::File`,
+/// since the caller (the editor's file-watcher) already has both on hand.
+pub fn discover_workspace_flows(files: &[(PathBuf, syn::File)]) -> WorkspaceFlows {
+    let mut tree = WorkspaceFlows::default();
+    for (path, file) in files {
+        let entries = discover_file_flows(path, file);
+        if !entries.is_empty() {
+            tree.files.push(FileFlows {
+                file: path.clone(),
+                entries,
+            });
+        }
+    }
+    tree
+}
+
+fn discover_file_flows(path: &Path, file: &syn::File) -> Vec<FlowEntry> {
+    file.items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Fn(item_fn) if returns_process_or_cluster(&item_fn.sig.output) => {
+                Some(FlowEntry {
+                    fn_name: item_fn.sig.ident.to_string(),
+                    file: path.to_path_buf(),
+                    directly_compilable: takes_only_flow_builder(&item_fn.sig.inputs),
+                })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Whether a return type mentions `Process` or `Cluster`, which is how every
+/// flow-producing function in this workspace signals its result - whether a
+/// bare `Process<'a, ()>` or a tuple like `(Process<'a, ()>, Cluster<'a, ()>)`.
+fn returns_process_or_cluster(output: &ReturnType) -> bool {
+    let ReturnType::Type(_, ty) = output else {
+        return false;
+    };
+    type_mentions(ty, "Process") || type_mentions(ty, "Cluster")
+}
+
+fn type_mentions(ty: &Type, ident: &str) -> bool {
+    match ty {
+        Type::Path(type_path) => type_path
+            .path
+            .segments
+            .iter()
+            .any(|segment| segment.ident == ident),
+        Type::Tuple(type_tuple) => type_tuple.elems.iter().any(|elem| type_mentions(elem, ident)),
+        Type::Reference(type_reference) => type_mentions(&type_reference.elem, ident),
+        _ => false,
+    }
+}
+
+fn takes_only_flow_builder(inputs: &syn::punctuated::Punctuated<FnArg, syn::token::Comma>) -> bool {
+    let mut iter = inputs.iter();
+    let Some(FnArg::Typed(only_arg)) = iter.next() else {
+        return false;
+    };
+    if iter.next().is_some() {
+        return false;
+    }
+    matches!(&*only_arg.ty, Type::Reference(type_reference) if type_mentions(&type_reference.elem, "FlowBuilder"))
+}
+
+/// Renders the snapshot-regeneration test for one directly-compilable
+/// [`FlowEntry`], in the same style as the hand-written `map_reduce_ir` test:
+/// build, optimize, then snapshot both `built.ir()` and every location's
+/// surface syntax.
+pub fn render_snapshot_test(entry: &FlowEntry) -> String {
+    if !entry.directly_compilable {
+        return format!(
+            "// `{}` takes parameters beyond a single `&FlowBuilder<'a>`, so its \
+             snapshot test can't be generated automatically - invoke it by hand \
+             with representative arguments first.",
+            entry.fn_name
+        );
+    }
+
+    format!(
+        r#"#[test]
+fn {fn_name}_ir() {{
+    let builder = hydro_lang::compile::builder::FlowBuilder::new();
+    let _ = super::{fn_name}(&builder);
+    let built = builder.with_default_optimize::<hydro_lang::deploy::HydroDeploy>();
+
+    hydro_build_utils::assert_debug_snapshot!(built.ir());
+
+    for (id, ir) in built.preview_compile().all_dfir() {{
+        hydro_build_utils::insta::with_settings!({{
+            snapshot_suffix => format!("surface_graph_{{id}}")
+        }}, {{
+            hydro_build_utils::assert_snapshot!(ir.surface_syntax_string());
+        }});
+    }}
+}}
+"#,
+        fn_name = entry.fn_name
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+
+    use super::*;
+
+    #[test]
+    fn returns_process_or_cluster_true_for_bare_and_tuple_returns() {
+        let bare: ReturnType = parse_quote!(-> Process<'a, ()>);
+        let tuple: ReturnType = parse_quote!(-> (Process<'a, ()>, Cluster<'a, ()>));
+
+        assert!(returns_process_or_cluster(&bare));
+        assert!(returns_process_or_cluster(&tuple));
+    }
+
+    #[test]
+    fn returns_process_or_cluster_false_for_unrelated_return() {
+        let unrelated: ReturnType = parse_quote!(-> usize);
+        assert!(!returns_process_or_cluster(&unrelated));
+    }
+
+    #[test]
+    fn takes_only_flow_builder_true_for_single_flow_builder_param() {
+        let item_fn: syn::ItemFn = parse_quote! {
+            fn map_reduce<'a>(flow: &FlowBuilder<'a>) -> (Process<'a, Leader>, Cluster<'a, Worker>) {}
+        };
+
+        assert!(takes_only_flow_builder(&item_fn.sig.inputs));
+    }
+
+    #[test]
+    fn takes_only_flow_builder_false_with_extra_params() {
+        // Mirrors `partition`, which takes two clusters and a policy on top of
+        // (implicitly) a `FlowBuilder`-derived `Cluster` - it can't be invoked
+        // with no arguments, so its snapshot test can't be auto-generated.
+        let item_fn: syn::ItemFn = parse_quote! {
+            fn partition<'a, F>(
+                cluster1: Cluster<'a, ()>,
+                cluster2: Cluster<'a, ()>,
+                dist_policy: F,
+            ) -> (Cluster<'a, ()>, Cluster<'a, ()>) {}
+        };
+
+        assert!(!takes_only_flow_builder(&item_fn.sig.inputs));
+    }
+
+    #[test]
+    fn render_snapshot_test_notes_when_not_directly_compilable() {
+        let entry = FlowEntry {
+            fn_name: "partition".to_string(),
+            file: PathBuf::from("simple_cluster.rs"),
+            directly_compilable: false,
+        };
+
+        assert!(render_snapshot_test(&entry).contains("can't be generated automatically"));
+    }
+}
@@ -0,0 +1,8 @@
+//! Source-level tooling: passes that work over a project's `.rs` files
+//! directly (via `syn`) rather than over compiled IR, because their targets -
+//! `#[hydro::flow]` functions, `nondet!`/`assume_ordering` call sites, flow
+//! entry points - only exist before compilation collapses them into IR.
+
+pub mod codelens;
+pub mod discovery;
+pub mod nondet_inspector;
@@ -0,0 +1,216 @@
+//! Collects every `nondet!`/`assume_ordering`/`assume_*` call site across the
+//! workspace so the justification prose written inside `nondet!(/** ... */)`
+//! can be surfaced on hover, and flags the ones that waive a determinism
+//! guarantee on a cross-location edge (e.g. the `assume_ordering` after
+//! `send_partitioned` in `partition`, or after `send_bincode` in
+//! `simple_cluster`) as a warning, since those are the assumptions a reviewer
+//! is least likely to have actually re-checked.
+
+use syn::visit::{self, Visit};
+use syn::{Expr, ExprMacro, ExprMethodCall};
+
+/// One `nondet!`/`assume_*` call site found in the workspace.
+#[derive(Debug, Clone)]
+pub struct NondetSite {
+    /// `"nondet"`, `"assume_ordering"`, `"assume_retries"`, etc.
+    pub macro_or_method: String,
+    /// The prose written inside the `nondet!(/** ... */)` doc-comment-style
+    /// justification, shown on hover.
+    pub justification: String,
+    /// Set when this is an `assume_*` call chained right after a network
+    /// operator (`send_bincode`, `send_partitioned`, `demux_bincode`,
+    /// `round_robin_bincode`, `decouple_cluster`, `decouple_process`), either
+    /// directly or through `entries`/`values`/`keys` - i.e. the assumption
+    /// waives a guarantee about a cross-location edge rather than a purely
+    /// local one.
+    pub on_cross_location_edge: bool,
+}
+
+/// A diagnostic emitted for a [`NondetSite`] that sits on a cross-location
+/// edge, so the assumption doesn't go unnoticed next to the wire calls it
+/// actually governs.
+#[derive(Debug, Clone)]
+pub struct NondetDiagnostic {
+    pub site: NondetSite,
+    pub message: String,
+}
+
+const NETWORK_METHODS: &[&str] = &[
+    "send_bincode",
+    "demux_bincode",
+    "round_robin_bincode",
+    "send_partitioned",
+    "decouple_cluster",
+    "decouple_process",
+];
+
+/// Methods that pass an edge's "is this a network call" classification
+/// through unchanged - they reshape a keyed/entry view of a stream but don't
+/// introduce or cross a location boundary themselves.
+const TRANSPARENT_METHODS: &[&str] = &["entries", "values", "keys"];
+
+/// Walks a parsed source file and collects every `nondet!`/`assume_*` site.
+pub fn collect_nondet_sites(file: &syn::File) -> Vec<NondetSite> {
+    let mut visitor = NondetVisitor { sites: Vec::new() };
+    visitor.visit_file(file);
+    visitor.sites
+}
+
+/// Filters a set of sites down to the ones that warrant a warning-level
+/// diagnostic: assumptions waived on a cross-location edge.
+pub fn diagnostics_for(sites: &[NondetSite]) -> Vec<NondetDiagnostic> {
+    sites
+        .iter()
+        .filter(|site| site.on_cross_location_edge)
+        .map(|site| NondetDiagnostic {
+            site: site.clone(),
+            message: format!(
+                "`{}` waives a determinism guarantee on a cross-location edge: {}",
+                site.macro_or_method, site.justification
+            ),
+        })
+        .collect()
+}
+
+struct NondetVisitor {
+    sites: Vec<NondetSite>,
+}
+
+impl<'ast> Visit<'ast> for NondetVisitor {
+    fn visit_expr_method_call(&mut self, method_call: &'ast ExprMethodCall) {
+        let method_name = method_call.method.to_string();
+        let mut recorded_nondet_arg = false;
+
+        if method_name == "assume_ordering" || method_name.starts_with("assume_") {
+            let on_cross_location_edge = is_network_call(&method_call.receiver);
+            if let Some(justification) = method_call
+                .args
+                .iter()
+                .find_map(|arg| nondet_justification(arg))
+            {
+                self.sites.push(NondetSite {
+                    macro_or_method: method_name,
+                    justification,
+                    on_cross_location_edge,
+                });
+                recorded_nondet_arg = true;
+            }
+        }
+
+        // Visit the receiver (and, if its `nondet!(...)` argument wasn't
+        // already recorded above as part of an `assume_*` call, the args too)
+        // so we don't double-count the same `nondet!` site with the wrong
+        // `on_cross_location_edge` classification.
+        self.visit_expr(&method_call.receiver);
+        if !recorded_nondet_arg {
+            for arg in &method_call.args {
+                self.visit_expr(arg);
+            }
+        }
+    }
+
+    fn visit_expr_macro(&mut self, expr_macro: &'ast ExprMacro) {
+        if expr_macro.mac.path.is_ident("nondet") {
+            self.sites.push(NondetSite {
+                macro_or_method: "nondet".to_string(),
+                justification: expr_macro.mac.tokens.to_string(),
+                // A `nondet!(...)` passed to an `assume_*` call is recorded
+                // and classified by `visit_expr_method_call` and never
+                // reaches here; every other one is recorded as not on an edge.
+                on_cross_location_edge: false,
+            });
+        }
+        visit::visit_expr_macro(self, expr_macro);
+    }
+}
+
+/// Pulls the justification text out of a `nondet!(/** ... */)` argument, if
+/// `arg` is such a macro call.
+fn nondet_justification(arg: &Expr) -> Option<String> {
+    if let Expr::Macro(expr_macro) = arg {
+        if expr_macro.mac.path.is_ident("nondet") {
+            return Some(expr_macro.mac.tokens.to_string());
+        }
+    }
+    None
+}
+
+/// Whether `receiver` is a direct call to one of the network methods that
+/// cross a location boundary, or a chain of [`TRANSPARENT_METHODS`] on top of
+/// one. The walk stops the moment it hits anything else, so a network call
+/// buried arbitrarily far up the same chain (behind a `fold`, `snapshot`,
+/// `reduce_commutative`, ...) is correctly *not* treated as "right after" a
+/// network operator.
+fn is_network_call(receiver: &Expr) -> bool {
+    match receiver {
+        Expr::MethodCall(method_call) => {
+            let method_name = method_call.method.to_string();
+            if NETWORK_METHODS.contains(&method_name.as_str()) {
+                true
+            } else if TRANSPARENT_METHODS.contains(&method_name.as_str()) {
+                is_network_call(&method_call.receiver)
+            } else {
+                false
+            }
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn site(on_cross_location_edge: bool) -> NondetSite {
+        NondetSite {
+            macro_or_method: "assume_ordering".to_string(),
+            justification: "testing, order does not matter".to_string(),
+            on_cross_location_edge,
+        }
+    }
+
+    #[test]
+    fn diagnostics_for_flags_only_cross_location_sites() {
+        let sites = vec![site(true), site(false)];
+        let diagnostics = diagnostics_for(&sites);
+
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].message.contains("cross-location edge"));
+    }
+
+    #[test]
+    fn is_network_call_true_directly_after_network_op() {
+        let expr: Expr = syn::parse_quote!(cluster1
+            .source_iter(q!(vec!(CLUSTER_SELF_ID)))
+            .map(q!(|id| id))
+            .send_partitioned(&cluster2, dist_policy));
+
+        assert!(is_network_call(&expr));
+    }
+
+    #[test]
+    fn is_network_call_true_through_transparent_entries() {
+        let expr: Expr = syn::parse_quote!(ids
+            .cross_product(numbers)
+            .demux_bincode(&cluster)
+            .send_bincode(&process)
+            .entries());
+
+        assert!(is_network_call(&expr));
+    }
+
+    #[test]
+    fn is_network_call_false_when_network_op_is_not_adjacent() {
+        // Mirrors `map_reduce`'s final `.snapshot(...).entries().all_ticks()`
+        // chain: `send_bincode` happened earlier in the pipeline, but
+        // `all_ticks` (not a network op, not transparent) sits right before
+        // the call in question, so this must not be flagged.
+        let expr: Expr = syn::parse_quote!(reduced
+            .send_bincode(&process)
+            .snapshot(&process.tick(), nondet!(/** intentional output */))
+            .entries()
+            .all_ticks());
+
+        assert!(!is_network_call(&expr));
+    }
+}